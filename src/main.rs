@@ -1,11 +1,15 @@
 use anyhow::anyhow;
 use anyhow::Result;
+use chrono::Datelike;
 use chrono::Local;
+use chrono::LocalResult;
 use chrono::NaiveDate;
 use chrono::NaiveDateTime;
 use chrono::NaiveTime;
 use chrono::TimeZone;
+use chrono::Timelike;
 use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
 use gloo_storage::Storage;
 use leptos::*;
 
@@ -13,7 +17,12 @@ use leptos_use::*;
 
 use thaw::*;
 
+use schedule::Schedule;
+use share::SharedConfig;
+
 mod protos;
+mod schedule;
+mod share;
 mod storage;
 
 // Treats the parameters as dates and returns the TimeDelta.
@@ -22,18 +31,46 @@ mod storage;
 //   from:   2024-09-30
 //   amount: 2024-09-28
 //   output: TimeDelta(days=2)
-fn subtract_dates(from: DateTime<Local>, amount: DateTime<Local>) -> TimeDelta {
+fn subtract_dates<Zone: TimeZone>(from: DateTime<Zone>, amount: DateTime<Zone>) -> TimeDelta {
     TimeDelta::days(1) * (from.date_naive() - amount.date_naive()).num_days() as i32
 }
 
-// Convert NaiveDateTime to DateTime<Local>.
-fn naive_datetime_to_local(naive_datetime: NaiveDateTime) -> Result<DateTime<Local>> {
-    let local_timezone = Local;
-    let mapped_time = local_timezone.from_local_datetime(&naive_datetime);
-    let local_datetime = mapped_time
-        .single()
-        .ok_or(anyhow!("no local time found in {naive_datetime}"))?;
-    Ok(local_datetime)
+// Whether the naive wall-clock time resolved to a single instant, or had to
+// be disambiguated because of a DST transition.
+enum LocalTimeResolution {
+    Unambiguous,
+    // The wall-clock time falls in a fall-back hour and maps to two
+    // instants; the earliest of the two was picked.
+    Ambiguous,
+}
+
+// Convert NaiveDateTime to a DateTime in the given timezone, resolving DST
+// transitions instead of failing on them: an ambiguous (fall-back) time
+// picks the earliest instant, and a nonexistent (spring-forward) time is
+// advanced past the gap.
+fn naive_datetime_to_local<Zone: TimeZone>(
+    naive_datetime: NaiveDateTime,
+    timezone: &Zone,
+) -> Result<(DateTime<Zone>, LocalTimeResolution)> {
+    match timezone.from_local_datetime(&naive_datetime) {
+        LocalResult::Single(local_datetime) => {
+            Ok((local_datetime, LocalTimeResolution::Unambiguous))
+        }
+        LocalResult::Ambiguous(earliest, _latest) => Ok((earliest, LocalTimeResolution::Ambiguous)),
+        LocalResult::None => {
+            // The naive time falls in a spring-forward gap. DST transitions
+            // are at most an hour, so advancing past it and re-resolving
+            // always lands on a valid instant.
+            let shifted = naive_datetime + TimeDelta::hours(1);
+            let local_datetime = timezone
+                .from_local_datetime(&shifted)
+                .single()
+                .ok_or(anyhow!(
+                    "no local time found in {naive_datetime} even after advancing past the DST gap"
+                ))?;
+            Ok((local_datetime, LocalTimeResolution::Unambiguous))
+        }
+    }
 }
 
 #[component]
@@ -53,13 +90,14 @@ fn DateTimeSet(
 // Component to use the current time as start time.
 #[component]
 fn SetCurrentTimeAsStartTime(
-    #[prop(into)] set_start_time: Callback<DateTime<Local>>,
+    #[prop(into)] set_start_time: Callback<DateTime<Tz>>,
+    timezone: ReadSignal<Tz>,
 ) -> impl IntoView {
     view! {
         <Button
             class="btn btn-primary"
             on:click=move |_| {
-                set_start_time.call(Local::now());
+                set_start_time.call(Utc::now().with_timezone(&timezone.get()));
             }
         >
             "現在時刻を開始時刻として保存"
@@ -67,6 +105,84 @@ fn SetCurrentTimeAsStartTime(
     }
 }
 
+// Component to pick the named timezone (e.g. "Asia/Tokyo") that the start
+// time and the daily rollover are computed in.
+#[component]
+fn TimezonePicker(timezone_rw_signal: RwSignal<Tz>) -> impl IntoView {
+    let (timezone, set_timezone) = timezone_rw_signal.split();
+
+    view! {
+        <div>
+            <label for="timezone-name">"タイムゾーン(IANA名 例: Asia/Tokyo):"</label>
+            <input
+                type="text"
+                id="timezone-name"
+                name="timezone-name"
+                prop:value=move || timezone.get().name().to_string()
+                on:input=move |ev| {
+                    let value = event_target_value(&ev);
+                    if let Ok(timezone) = value.parse::<Tz>() {
+                        set_timezone.set(timezone);
+                    } else {
+                        logging::error!("Unknown timezone name: '{value}'");
+                    }
+                }
+            />
+
+        </div>
+    }
+}
+
+// Component to pick an optional schedule restricting which weekdays/hours
+// sessions start on, e.g. "Mon..Fri 7..17/2". An empty input disables the
+// schedule entirely, falling back to a single session a day.
+#[component]
+fn SchedulePicker(schedule_rw_signal: RwSignal<Option<Schedule>>) -> impl IntoView {
+    let (schedule, set_schedule) = schedule_rw_signal.split();
+
+    view! {
+        <div>
+            <label for="schedule">
+                "スケジュール(曜日 時間、例: Mon..Fri 7..17/2。空欄で無効):"
+            </label>
+            <input
+                type="text"
+                id="schedule"
+                name="schedule"
+                prop:value=move || {
+                    schedule.get().map(|schedule| schedule.to_string()).unwrap_or_default()
+                }
+                on:input=move |ev| {
+                    let value = event_target_value(&ev);
+                    if value.is_empty() {
+                        set_schedule.set(None);
+                        return;
+                    }
+                    if let Ok(schedule) = value.parse::<Schedule>() {
+                        set_schedule.set(Some(schedule));
+                    } else {
+                        logging::error!("Failed to parse schedule: '{value}'");
+                    }
+                }
+            />
+
+        </div>
+    }
+}
+
+// Component showing the current page URL with the start time, interval,
+// timezone and schedule encoded as a `config` query parameter, so it can be
+// copied and shared or reopened on another device.
+#[component]
+fn ShareConfig(#[prop(into)] export_url: Signal<String>) -> impl IntoView {
+    view! {
+        <div>
+            <label for="export-url">"共有用URL(コピーして共有/別端末で開く):"</label>
+            <input type="text" id="export-url" readonly=true prop:value=move || export_url.get() />
+        </div>
+    }
+}
+
 #[component]
 fn Interval(interval_rw_signal: RwSignal<TimeDelta>) -> impl IntoView {
     // TODO: add a tooltip.
@@ -100,30 +216,222 @@ fn Interval(interval_rw_signal: RwSignal<TimeDelta>) -> impl IntoView {
     }
 }
 
+// Computes today's session start time(s). Without a schedule this is a
+// single time, just like before. With a schedule, no times are returned on
+// an inactive weekday, the per-day interval only advances across active
+// days, and one time is returned per active hour-of-day.
+fn todays_start_times(
+    initial_start_time: DateTime<Tz>,
+    now: DateTime<Tz>,
+    interval: TimeDelta,
+    schedule: Option<&Schedule>,
+) -> Vec<DateTime<Tz>> {
+    if let Some(schedule) = schedule {
+        if !schedule.is_active_on(now.weekday()) {
+            return Vec::new();
+        }
+    }
+
+    let days_since_start = subtract_dates(now, initial_start_time);
+    if days_since_start.num_days() < 0 {
+        return Vec::new();
+    }
+
+    let active_days_elapsed = match schedule {
+        Some(schedule) => {
+            (0..=days_since_start.num_days())
+                .filter(|&day_offset| {
+                    schedule
+                        .is_active_on((initial_start_time + TimeDelta::days(day_offset)).weekday())
+                })
+                .count() as i64
+                - 1
+        }
+        None => days_since_start.num_days(),
+    };
+
+    let offset = interval * active_days_elapsed.max(0) as i32;
+    let base_start_time = initial_start_time + days_since_start + offset;
+
+    match schedule.map(|schedule| schedule.hours().collect::<Vec<_>>()) {
+        Some(hours) if !hours.is_empty() => hours
+            .into_iter()
+            .filter_map(|hour| {
+                // `DateTime::with_hour` only succeeds for `LocalResult::Single`,
+                // so a scheduled hour that falls in a spring-forward gap
+                // would otherwise be silently dropped. Go through
+                // `naive_datetime_to_local` instead, so a scheduled hour is
+                // resolved the same way a picked start time is: advanced
+                // past the gap rather than skipped.
+                let naive = base_start_time.naive_local().with_hour(hour)?;
+                naive_datetime_to_local(naive, &base_start_time.timezone())
+                    .ok()
+                    .map(|(local_datetime, _)| local_datetime)
+            })
+            .collect(),
+        _ => vec![base_start_time],
+    }
+}
+
+// Formats a TimeDelta as "HH:MM:SS". Negative deltas are clamped to zero;
+// callers are expected to only pass elapsed/remaining durations.
+fn format_hms(delta: TimeDelta) -> String {
+    let total_seconds = delta.num_seconds().max(0);
+    format!(
+        "{:02}:{:02}:{:02}",
+        total_seconds / 3600,
+        (total_seconds % 3600) / 60,
+        total_seconds % 60
+    )
+}
+
+// Shows the browser's permission-granted notification, if the user has
+// allowed it. Silently does nothing otherwise, since asking again without a
+// user gesture would not do anything anyway.
+fn send_start_time_notification() {
+    if web_sys::Notification::permission() != web_sys::NotificationPermission::Granted {
+        return;
+    }
+    let _ = web_sys::Notification::new("聖遺物マラソン開始時刻になりました");
+}
+
+// Checkbox to opt in to a one-a-day browser notification fired when a
+// start time is reached. Notification permission can only be requested
+// from a user gesture, so it's requested right when the user opts in.
+#[component]
+fn NotifyToggle(notify_rw_signal: RwSignal<bool>) -> impl IntoView {
+    let (notify, set_notify) = notify_rw_signal.split();
+
+    view! {
+        <div>
+            <label for="notify-enabled">"開始時刻になったら通知する:"</label>
+            <input
+                type="checkbox"
+                id="notify-enabled"
+                name="notify-enabled"
+                prop:checked=move || notify.get()
+                on:change=move |ev| {
+                    let checked = event_target_checked(&ev);
+                    if checked {
+                        let _ = web_sys::Notification::request_permission();
+                    }
+                    set_notify.set(checked);
+                }
+            />
+
+        </div>
+    }
+}
+
+// Live countdown to the next of today's start times ("あと HH:MM:SS"),
+// ticking once a second. Once every one of today's start times has passed,
+// shows the elapsed time since the most recent one instead of going
+// negative. Also fires the one-a-day notification the moment a start time
+// is crossed, if the user opted in.
 #[component]
-fn StartTimeToday(
+fn Countdown(
     #[prop(into)] get_initial_start_time: Callback<(), Option<DateTime<Utc>>>,
     interval: ReadSignal<TimeDelta>,
+    timezone: ReadSignal<Tz>,
+    schedule: ReadSignal<Option<Schedule>>,
+    notify_enabled: ReadSignal<bool>,
 ) -> impl IntoView {
-    fn todays_start_time(
-        initial_start_time: DateTime<Local>,
-        interval: TimeDelta,
-    ) -> Option<DateTime<Local>> {
-        let days_since_start = subtract_dates(Local::now(), initial_start_time);
-        if days_since_start.num_days() < 0 {
-            return None;
+    let now_in_timezone = move || Utc::now().with_timezone(&timezone.get());
+    let (current_time, set_current_time) = create_signal(now_in_timezone());
+    use_interval_fn(
+        move || {
+            set_current_time.set(now_in_timezone());
+        },
+        1000,
+    );
+
+    // The previous tick's timestamp, so a start time is detected as
+    // "crossed" if it falls anywhere since the last tick, not just within a
+    // fixed window of this one. Backgrounded tabs throttle `setInterval` to
+    // far less than once a second, so a fixed window would miss crossings
+    // that happen while the tab isn't in the foreground.
+    let previous_tick = create_rw_signal(None::<DateTime<Tz>>);
+
+    create_effect(move |_| {
+        let now = current_time.get();
+        let previous_now = previous_tick.get_untracked();
+        previous_tick.set_untracked(Some(now));
+
+        if !notify_enabled.get() {
+            return;
+        }
+        let Some(previous_now) = previous_now else {
+            return;
+        };
+        let Some(initial_start_time) = get_initial_start_time.call(()) else {
+            return;
+        };
+        let initial_start_time = initial_start_time.with_timezone(&timezone.get());
+        let schedule = schedule.get();
+        let start_times =
+            todays_start_times(initial_start_time, now, interval.get(), schedule.as_ref());
+        let just_crossed = start_times
+            .iter()
+            .any(|&start_time| start_time > previous_now && start_time <= now);
+        if !just_crossed {
+            return;
         }
 
-        let offset = interval * days_since_start.num_days() as i32;
-        Some(initial_start_time + days_since_start + offset)
+        let mut storage = storage::Storage::new();
+        let today = now.date_naive();
+        if storage.get_last_notified_date() == Some(today) {
+            return;
+        }
+        send_start_time_notification();
+        let _ = storage.set_last_notified_date(today);
+    });
+
+    view! {
+        <div>
+            "次の開始時刻まで:"
+            {move || {
+                let now = current_time.get();
+                let Some(initial_start_time) = get_initial_start_time.call(()) else {
+                    return "不明".to_string();
+                };
+                let initial_start_time = initial_start_time.with_timezone(&timezone.get());
+                let schedule = schedule.get();
+                let start_times = todays_start_times(
+                    initial_start_time,
+                    now,
+                    interval.get(),
+                    schedule.as_ref(),
+                );
+                match start_times.iter().filter(|&&start_time| start_time > now).min() {
+                    Some(&next) => format!("あと {}", format_hms(next - now)),
+                    None => {
+                        match start_times.iter().max() {
+                            Some(&last) => format!("経過 {}", format_hms(now - last)),
+                            None => "該当なし".to_string(),
+                        }
+                    }
+                }
+            }}
+
+        </div>
     }
+}
+
+#[component]
+fn StartTimeToday(
+    #[prop(into)] get_initial_start_time: Callback<(), Option<DateTime<Utc>>>,
+    interval: ReadSignal<TimeDelta>,
+    timezone: ReadSignal<Tz>,
+    schedule: ReadSignal<Option<Schedule>>,
+) -> impl IntoView {
+    let now_in_timezone = move || Utc::now().with_timezone(&timezone.get());
 
-    let (date_today, set_date_today) = create_signal(Local::now());
+    let (date_today, set_date_today) = create_signal(now_in_timezone());
 
-    let (current_time, set_current_time) = create_signal(Local::now());
+    let (current_time, set_current_time) = create_signal(now_in_timezone());
     use_interval_fn(
         move || {
-            set_current_time.set(Local::now());
+            set_current_time.set(now_in_timezone());
         },
         1000,
     );
@@ -153,12 +461,22 @@ fn StartTimeToday(
                     let Some(initial_start_time) = initial_start_time else {
                         return "不明".to_string();
                     };
-                    let initial_start_time: DateTime<Local> = DateTime::from(initial_start_time);
-                    let start_local_time = todays_start_time(initial_start_time, interval);
-                    if let Some(start_local_time) = start_local_time {
-                        start_local_time.format("%H:%M:%S").to_string()
+                    let initial_start_time = initial_start_time.with_timezone(&timezone.get());
+                    let schedule = schedule.get();
+                    let start_local_times = todays_start_times(
+                        initial_start_time,
+                        now_in_timezone(),
+                        interval,
+                        schedule.as_ref(),
+                    );
+                    if start_local_times.is_empty() {
+                        "該当なし".to_string()
                     } else {
-                        "不明".to_string()
+                        start_local_times
+                            .iter()
+                            .map(|start_local_time| start_local_time.format("%H:%M:%S").to_string())
+                            .collect::<Vec<_>>()
+                            .join(", ")
                     }
                 }}
 
@@ -220,24 +538,62 @@ fn DebugFeatures() -> impl IntoView {
 
 fn main() {
     let storage = storage::Storage::new();
-    let interval_rw_signal: RwSignal<TimeDelta> =
-        create_rw_signal(storage.get_start_interval().unwrap_or(TimeDelta::zero()));
+    // Imported config from the `config` query parameter, if the page was
+    // opened via a shared/exported URL. Falls back to the LocalStorage
+    // values below when absent or when it fails to parse.
+    let imported_config = use_window()
+        .location()
+        .search()
+        .ok()
+        .and_then(|search| SharedConfig::from_query_string(&search));
+
+    let interval_rw_signal: RwSignal<TimeDelta> = create_rw_signal(
+        imported_config
+            .as_ref()
+            .map(|config| config.interval)
+            .or(storage.get_start_interval())
+            .unwrap_or(TimeDelta::zero()),
+    );
+    let timezone_rw_signal: RwSignal<Tz> = create_rw_signal(
+        imported_config
+            .as_ref()
+            .map(|config| config.timezone)
+            .or(storage.get_timezone())
+            .unwrap_or(chrono_tz::UTC),
+    );
+    let schedule_rw_signal: RwSignal<Option<Schedule>> = create_rw_signal(
+        imported_config
+            .as_ref()
+            .map(|config| config.schedule.clone())
+            .unwrap_or(storage.get_schedule()),
+    );
+    // Notification opt-in isn't part of the shareable config: it's a
+    // per-browser preference, not something you'd want a shared link to
+    // silently turn on for someone else.
+    let notify_rw_signal: RwSignal<bool> = create_rw_signal(storage.get_notifications_enabled());
 
     // TODO: Clean this up. Return both date and time?
-    let start_time = storage.get_start_time();
-    let date_signal = start_time.map(|start_time| {
-        let start_time: DateTime<Local> = DateTime::from(start_time);
-        start_time.date_naive()
-    });
-    let time_signal = start_time.map(|start_time| {
-        let start_time: DateTime<Local> = DateTime::from(start_time);
-        start_time.time()
-    });
+    let start_time = imported_config
+        .as_ref()
+        .map(|config| config.start_time)
+        .or(storage.get_start_time());
+    // Rendered in the user's chosen timezone, not the machine's local zone:
+    // the picker and the rest of the page (StartTimeToday, Countdown) must
+    // agree on what wall-clock time was picked, or an innocent re-save here
+    // would silently shift the real UTC start time.
+    let timezone = timezone_rw_signal.get_untracked();
+    let date_signal = start_time.map(|start_time| start_time.with_timezone(&timezone).date_naive());
+    let time_signal = start_time.map(|start_time| start_time.with_timezone(&timezone).time());
 
     // These are the source of truth for the start time.
     let date_signal: RwSignal<Option<NaiveDate>> = RwSignal::new(date_signal);
     let time_signal: RwSignal<Option<NaiveTime>> = RwSignal::new(time_signal);
 
+    // Set when the most recently picked date/time fell in a DST fall-back
+    // hour, so the UI can tell the user the earliest of the two instants was
+    // used.
+    let start_time_was_ambiguous = create_rw_signal(false);
+
     // Memoized drived signal for calculating the initial start time in UTC, when the user
     // interacts with the DatePicker or the TimePicker.
     // Note that the start time is saved to local storage.
@@ -250,11 +606,12 @@ fn main() {
             return None;
         };
         let new_date_time = NaiveDateTime::new(new_date, new_time);
-        let local_date_time = naive_datetime_to_local(new_date_time);
-        let Ok(local_date_time) = local_date_time else {
+        let local_date_time = naive_datetime_to_local(new_date_time, &timezone_rw_signal.get());
+        let Ok((local_date_time, resolution)) = local_date_time else {
             logging::error!("local_date_time is None");
             return None;
         };
+        start_time_was_ambiguous.set(matches!(resolution, LocalTimeResolution::Ambiguous));
 
         let utc_date_time = local_date_time.to_utc();
         Some(utc_date_time)
@@ -275,11 +632,52 @@ fn main() {
         storage.set_start_interval(interval)
     });
 
-    let set_start_time = move |new_time: DateTime<Local>| {
+    create_effect(move |_| {
+        let timezone = timezone_rw_signal.get();
+        let mut storage = storage::Storage::new();
+        storage.set_timezone(timezone)
+    });
+
+    create_effect(move |_| {
+        let schedule = schedule_rw_signal.get();
+        let mut storage = storage::Storage::new();
+        storage.set_schedule(schedule.as_ref())
+    });
+
+    create_effect(move |_| {
+        let notify = notify_rw_signal.get();
+        let mut storage = storage::Storage::new();
+        storage.set_notifications_enabled(notify)
+    });
+
+    let set_start_time = move |new_time: DateTime<Tz>| {
         date_signal.set(Some(new_time.date_naive()));
         time_signal.set(Some(new_time.time()));
     };
 
+    // The current page URL (without query parameters), used as the base for
+    // the exported share URL.
+    let base_url = {
+        let location = use_window().location();
+        format!(
+            "{}{}",
+            location.origin().unwrap_or_default(),
+            location.pathname().unwrap_or_default()
+        )
+    };
+    let export_url = create_memo(move |_| {
+        let Some(start_time) = get_initial_start_time.get() else {
+            return base_url.clone();
+        };
+        let config = SharedConfig {
+            start_time,
+            interval: interval_rw_signal.get(),
+            timezone: timezone_rw_signal.get(),
+            schedule: schedule_rw_signal.get(),
+        };
+        format!("{base_url}{}", config.to_query_string())
+    });
+
     mount_to_body(move || {
         view! {
             <h1>"聖遺物マラソン開始時間計算"</h1>
@@ -287,12 +685,31 @@ fn main() {
                 <StartTimeToday
                     get_initial_start_time=move |_| get_initial_start_time.get()
                     interval=interval_rw_signal.read_only()
+                    timezone=timezone_rw_signal.read_only()
+                    schedule=schedule_rw_signal.read_only()
                 />
             </h2>
+            <Countdown
+                get_initial_start_time=move |_| get_initial_start_time.get()
+                interval=interval_rw_signal.read_only()
+                timezone=timezone_rw_signal.read_only()
+                schedule=schedule_rw_signal.read_only()
+                notify_enabled=notify_rw_signal.read_only()
+            />
 
             <DateTimeSet date_signal time_signal />
-            <SetCurrentTimeAsStartTime set_start_time />
+            <div hidden=move || !start_time_was_ambiguous.get()>
+                "選択した時刻は夏時間の切り替わりで一意に定まらないため、早い方の時刻を開始時刻として使用しています。"
+            </div>
+            <SetCurrentTimeAsStartTime
+                set_start_time
+                timezone=timezone_rw_signal.read_only()
+            />
             <Interval interval_rw_signal=interval_rw_signal />
+            <TimezonePicker timezone_rw_signal=timezone_rw_signal />
+            <SchedulePicker schedule_rw_signal=schedule_rw_signal />
+            <NotifyToggle notify_rw_signal=notify_rw_signal />
+            <ShareConfig export_url=export_url />
             <hr />
 
             <DebugFeatures />
@@ -302,6 +719,8 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use chrono_tz::UTC;
+
     use super::*;
 
     #[test]
@@ -326,4 +745,115 @@ mod tests {
         let end = Local.ymd(2023, 7, 2).and_hms(23, 59, 59);
         assert_eq!(subtract_dates(start, end), TimeDelta::days(1));
     }
+
+    #[test]
+    fn todays_start_times_without_schedule_returns_single_time() {
+        let initial_start_time = UTC.with_ymd_and_hms(2024, 9, 30, 9, 0, 0).unwrap();
+        let now = UTC.with_ymd_and_hms(2024, 10, 2, 15, 0, 0).unwrap();
+        let times = todays_start_times(initial_start_time, now, TimeDelta::seconds(30), None);
+        assert_eq!(
+            times,
+            vec![UTC.with_ymd_and_hms(2024, 10, 2, 9, 1, 0).unwrap()]
+        );
+    }
+
+    #[test]
+    fn todays_start_times_returns_empty_on_inactive_weekday() {
+        // 2024-09-30 is a Monday; schedule only runs on weekends.
+        let initial_start_time = UTC.with_ymd_and_hms(2024, 9, 30, 9, 0, 0).unwrap();
+        let now = initial_start_time;
+        let schedule: Schedule = "Sat..Sun *".parse().unwrap();
+        let times = todays_start_times(
+            initial_start_time,
+            now,
+            TimeDelta::seconds(30),
+            Some(&schedule),
+        );
+        assert!(times.is_empty());
+    }
+
+    #[test]
+    fn todays_start_times_only_advances_interval_on_active_days() {
+        // 2024-09-30 is a Monday; schedule restricts to weekdays, so the
+        // 2024-10-05/06 weekend should not advance the interval.
+        let initial_start_time = UTC.with_ymd_and_hms(2024, 9, 30, 9, 0, 0).unwrap();
+        let schedule: Schedule = "Mon..Fri 9".parse().unwrap();
+        // 2024-10-07 is a Monday, 5 active days after the Monday start
+        // (Tue,Wed,Thu,Fri the first week, then this Monday), i.e. 5
+        // interval advances, not 7.
+        let now = UTC.with_ymd_and_hms(2024, 10, 7, 9, 0, 0).unwrap();
+        let times = todays_start_times(
+            initial_start_time,
+            now,
+            TimeDelta::minutes(1),
+            Some(&schedule),
+        );
+        assert_eq!(
+            times,
+            vec![UTC.with_ymd_and_hms(2024, 10, 7, 9, 5, 0).unwrap()]
+        );
+    }
+
+    #[test]
+    fn todays_start_times_expands_multiple_hours() {
+        let initial_start_time = UTC.with_ymd_and_hms(2024, 9, 30, 9, 0, 0).unwrap();
+        let now = initial_start_time;
+        let schedule: Schedule = "* 7,12,18".parse().unwrap();
+        let times = todays_start_times(
+            initial_start_time,
+            now,
+            TimeDelta::seconds(0),
+            Some(&schedule),
+        );
+        assert_eq!(
+            times,
+            vec![
+                UTC.with_ymd_and_hms(2024, 9, 30, 7, 0, 0).unwrap(),
+                UTC.with_ymd_and_hms(2024, 9, 30, 12, 0, 0).unwrap(),
+                UTC.with_ymd_and_hms(2024, 9, 30, 18, 0, 0).unwrap(),
+            ]
+        );
+    }
+
+    #[test]
+    fn naive_datetime_to_local_picks_earliest_on_ambiguous_fall_back() {
+        // America/New_York falls back from EDT to EST at 2024-11-03 02:00,
+        // so 01:30 occurs twice: once at 05:30 UTC (EDT), once at 06:30 UTC
+        // (EST).
+        let timezone = chrono_tz::America::New_York;
+        let naive = NaiveDate::from_ymd_opt(2024, 11, 3)
+            .unwrap()
+            .and_hms_opt(1, 30, 0)
+            .unwrap();
+        let (local_datetime, resolution) = naive_datetime_to_local(naive, &timezone).unwrap();
+        assert!(matches!(resolution, LocalTimeResolution::Ambiguous));
+        assert_eq!(
+            local_datetime,
+            timezone.from_local_datetime(&naive).earliest().unwrap()
+        );
+        assert_eq!(
+            local_datetime.to_utc(),
+            Utc.with_ymd_and_hms(2024, 11, 3, 5, 30, 0).unwrap()
+        );
+    }
+
+    #[test]
+    fn naive_datetime_to_local_advances_past_nonexistent_spring_forward_gap() {
+        // America/New_York springs forward from EST to EDT at 2024-03-10
+        // 02:00, skipping straight to 03:00, so 02:30 never occurs.
+        let timezone = chrono_tz::America::New_York;
+        let naive = NaiveDate::from_ymd_opt(2024, 3, 10)
+            .unwrap()
+            .and_hms_opt(2, 30, 0)
+            .unwrap();
+        let (local_datetime, resolution) = naive_datetime_to_local(naive, &timezone).unwrap();
+        assert!(matches!(resolution, LocalTimeResolution::Unambiguous));
+        assert_eq!(
+            local_datetime.naive_local(),
+            NaiveDate::from_ymd_opt(2024, 3, 10)
+                .unwrap()
+                .and_hms_opt(3, 30, 0)
+                .unwrap()
+        );
+    }
 }
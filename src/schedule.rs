@@ -0,0 +1,219 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use chrono::Weekday;
+
+const ALL_WEEKDAYS: [Weekday; 7] = [
+    Weekday::Mon,
+    Weekday::Tue,
+    Weekday::Wed,
+    Weekday::Thu,
+    Weekday::Fri,
+    Weekday::Sat,
+    Weekday::Sun,
+];
+
+// A schedule of active weekdays and active hours-of-day, e.g. "only advance
+// on weekdays" or "two runs a day". Parsed from a string of the form
+// "<weekdays> <hours>", where each half is a comma-separated list of terms.
+// Each term is a single value, an inclusive "lo..hi" range, or a stepped
+// "lo..hi/step" range (e.g. "7..17/2" expands to "7,9,11,13,15,17"). "*"
+// means "all".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Schedule {
+    // `chrono::Weekday` doesn't implement `Ord`, so active weekdays are kept
+    // as their Monday-based ordinal (0..=6) instead, and mapped back through
+    // `ALL_WEEKDAYS` wherever a `Weekday` is needed.
+    weekdays: BTreeSet<u8>,
+    hours: BTreeSet<u32>,
+}
+
+impl Schedule {
+    pub fn is_active_on(&self, weekday: Weekday) -> bool {
+        self.weekdays
+            .contains(&(weekday.num_days_from_monday() as u8))
+    }
+
+    pub fn hours(&self) -> impl Iterator<Item = u32> + '_ {
+        self.hours.iter().copied()
+    }
+}
+
+impl FromStr for Schedule {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let mut terms = s.split_whitespace();
+        let weekdays_term = terms
+            .next()
+            .ok_or(anyhow!("schedule '{s}' is missing a weekday term"))?;
+        let hours_term = terms
+            .next()
+            .ok_or(anyhow!("schedule '{s}' is missing an hour term"))?;
+        if terms.next().is_some() {
+            return Err(anyhow!(
+                "schedule '{s}' has more than the two expected 'weekdays hours' terms"
+            ));
+        }
+
+        let weekdays = parse_term_list(weekdays_term, ALL_WEEKDAYS.len(), parse_weekday)?
+            .into_iter()
+            .map(|ordinal| ordinal as u8)
+            .collect();
+        let hours = parse_term_list(hours_term, 24, parse_hour)?
+            .into_iter()
+            .map(|ordinal| ordinal as u32)
+            .collect();
+
+        Ok(Schedule { weekdays, hours })
+    }
+}
+
+impl fmt::Display for Schedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.weekdays.len() == ALL_WEEKDAYS.len() {
+            write!(f, "*")?;
+        } else {
+            let weekdays = ALL_WEEKDAYS
+                .iter()
+                .enumerate()
+                .filter(|(ordinal, _)| self.weekdays.contains(&(*ordinal as u8)))
+                .map(|(_, weekday)| weekday.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            write!(f, "{weekdays}")?;
+        }
+        write!(f, " ")?;
+        if self.hours.len() == 24 {
+            write!(f, "*")
+        } else {
+            let hours = self
+                .hours
+                .iter()
+                .map(|hour| hour.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            write!(f, "{hours}")
+        }
+    }
+}
+
+// Expands a comma-separated list of terms into the set of ordinals (indices
+// into a `domain_size`-element domain) it refers to.
+fn parse_term_list(
+    spec: &str,
+    domain_size: usize,
+    parse_value: impl Fn(&str) -> Result<usize>,
+) -> Result<BTreeSet<usize>> {
+    if spec == "*" {
+        return Ok((0..domain_size).collect());
+    }
+
+    let mut ordinals = BTreeSet::new();
+    for term in spec.split(',') {
+        if let Some((range, step)) = term.split_once('/') {
+            let step: usize = step
+                .parse()
+                .map_err(|_| anyhow!("invalid step in term '{term}'"))?;
+            if step == 0 {
+                return Err(anyhow!("step cannot be 0 in term '{term}'"));
+            }
+            let (lo, hi) = parse_range(range, &parse_value)?;
+            ordinals.extend((lo..=hi).step_by(step));
+        } else if term.contains("..") {
+            let (lo, hi) = parse_range(term, &parse_value)?;
+            ordinals.extend(lo..=hi);
+        } else {
+            ordinals.insert(parse_value(term)?);
+        }
+    }
+    Ok(ordinals)
+}
+
+fn parse_range(range: &str, parse_value: impl Fn(&str) -> Result<usize>) -> Result<(usize, usize)> {
+    let (lo, hi) = range
+        .split_once("..")
+        .ok_or(anyhow!("expected a 'lo..hi' range in '{range}'"))?;
+    let lo = parse_value(lo)?;
+    let hi = parse_value(hi)?;
+    if hi < lo {
+        return Err(anyhow!("range '{range}' has hi < lo"));
+    }
+    Ok((lo, hi))
+}
+
+fn parse_weekday(token: &str) -> Result<usize> {
+    let weekday: Weekday = token
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("unknown weekday '{token}'"))?;
+    Ok(weekday.num_days_from_monday() as usize)
+}
+
+fn parse_hour(token: &str) -> Result<usize> {
+    let hour: u32 = token
+        .trim()
+        .parse()
+        .map_err(|_| anyhow!("invalid hour '{token}'"))?;
+    // Clamp rather than reject, so a slightly-too-wide range like `0..24`
+    // still does what the user meant.
+    Ok(hour.min(23) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_wildcard_as_everything() {
+        let schedule: Schedule = "* *".parse().unwrap();
+        assert!(schedule.is_active_on(Weekday::Mon));
+        assert!(schedule.is_active_on(Weekday::Sun));
+        assert_eq!(schedule.hours().count(), 24);
+    }
+
+    #[test]
+    fn parses_weekday_range() {
+        let schedule: Schedule = "Mon..Fri *".parse().unwrap();
+        assert!(schedule.is_active_on(Weekday::Mon));
+        assert!(schedule.is_active_on(Weekday::Fri));
+        assert!(!schedule.is_active_on(Weekday::Sat));
+        assert!(!schedule.is_active_on(Weekday::Sun));
+    }
+
+    #[test]
+    fn parses_stepped_hour_range() {
+        let schedule: Schedule = "* 7..17/2".parse().unwrap();
+        let mut hours: Vec<u32> = schedule.hours().collect();
+        hours.sort();
+        assert_eq!(hours, vec![7, 9, 11, 13, 15, 17]);
+    }
+
+    #[test]
+    fn parses_comma_separated_list() {
+        let schedule: Schedule = "Mon,Wed,Fri 9,18".parse().unwrap();
+        assert!(schedule.is_active_on(Weekday::Mon));
+        assert!(!schedule.is_active_on(Weekday::Tue));
+        let mut hours: Vec<u32> = schedule.hours().collect();
+        hours.sort();
+        assert_eq!(hours, vec![9, 18]);
+    }
+
+    #[test]
+    fn rejects_zero_step() {
+        assert!("* 7..17/0".parse::<Schedule>().is_err());
+    }
+
+    #[test]
+    fn rejects_hi_less_than_lo() {
+        assert!("* 17..7".parse::<Schedule>().is_err());
+    }
+
+    #[test]
+    fn clamps_out_of_range_hour() {
+        let schedule: Schedule = "* 0..24".parse().unwrap();
+        assert_eq!(schedule.hours().count(), 24);
+    }
+}
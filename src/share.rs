@@ -0,0 +1,166 @@
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, TimeDelta, Utc};
+use chrono_tz::Tz;
+
+use super::schedule::Schedule;
+
+const QUERY_PARAM: &str = "config";
+
+// A snapshot of the user's marathon configuration that can be exported to
+// (and re-imported from) a single URL query parameter, so it can be shared
+// with a friend or carried over to another device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SharedConfig {
+    pub start_time: DateTime<Utc>,
+    pub interval: TimeDelta,
+    pub timezone: Tz,
+    pub schedule: Option<Schedule>,
+}
+
+impl SharedConfig {
+    // Builds the full query string (including the leading `?`) to append to
+    // a URL in order to share this config.
+    pub fn to_query_string(&self) -> String {
+        format!("?{QUERY_PARAM}={}", percent_encode(&self.encode()))
+    }
+
+    // Reads the `config` parameter out of a `Location::search()`-style query
+    // string (leading `?` optional) and decodes it. Returns None if the
+    // parameter is missing or fails to parse, so the caller can fall back to
+    // the existing LocalStorage value.
+    pub fn from_query_string(query: &str) -> Option<Self> {
+        let encoded = query.trim_start_matches('?').split('&').find_map(|pair| {
+            let (key, value) = pair.split_once('=')?;
+            (key == QUERY_PARAM).then_some(value)
+        })?;
+        let decoded = percent_decode(encoded).ok()?;
+        Self::decode(&decoded).ok()
+    }
+
+    // Encodes start time, interval, timezone and schedule into a compact,
+    // "~"-delimited string. chrono round-trips `to_rfc3339()` <->
+    // `parse_from_rfc3339()` losslessly (including the offset), so the
+    // imported start instant is identical to the exported one.
+    fn encode(&self) -> String {
+        [
+            self.start_time.to_rfc3339(),
+            self.interval.num_seconds().to_string(),
+            self.timezone.name().to_string(),
+            self.schedule
+                .as_ref()
+                .map(Schedule::to_string)
+                .unwrap_or_default(),
+        ]
+        .join("~")
+    }
+
+    fn decode(encoded: &str) -> Result<Self> {
+        let mut fields = encoded.splitn(4, '~');
+        let start_time = fields
+            .next()
+            .ok_or(anyhow!("config is missing a start time"))?;
+        let interval = fields
+            .next()
+            .ok_or(anyhow!("config is missing an interval"))?;
+        let timezone = fields
+            .next()
+            .ok_or(anyhow!("config is missing a timezone"))?;
+        let schedule = fields.next().unwrap_or_default();
+
+        let start_time = DateTime::parse_from_rfc3339(start_time)
+            .context("Failed to parse start time")?
+            .with_timezone(&Utc);
+        let interval = TimeDelta::seconds(interval.parse().context("Failed to parse interval")?);
+        let timezone: Tz = timezone
+            .parse()
+            .map_err(|_| anyhow!("unknown timezone '{timezone}'"))?;
+        let schedule = if schedule.is_empty() {
+            None
+        } else {
+            Some(schedule.parse().context("Failed to parse schedule")?)
+        };
+
+        Ok(SharedConfig {
+            start_time,
+            interval,
+            timezone,
+            schedule,
+        })
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+fn percent_decode(value: &str) -> Result<String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut remaining = value.bytes();
+    while let Some(byte) = remaining.next() {
+        if byte != b'%' {
+            bytes.push(byte);
+            continue;
+        }
+        let hi = remaining
+            .next()
+            .ok_or(anyhow!("truncated percent-encoding in '{value}'"))?;
+        let lo = remaining
+            .next()
+            .ok_or(anyhow!("truncated percent-encoding in '{value}'"))?;
+        let hex = std::str::from_utf8(&[hi, lo]).context("invalid percent-encoding")?;
+        bytes.push(u8::from_str_radix(hex, 16).context("invalid percent-encoding")?);
+    }
+    String::from_utf8(bytes).context("decoded config is not valid UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn sample_config() -> SharedConfig {
+        SharedConfig {
+            start_time: Utc.with_ymd_and_hms(2024, 9, 30, 9, 0, 0).unwrap(),
+            interval: TimeDelta::seconds(30),
+            timezone: chrono_tz::Asia::Tokyo,
+            schedule: Some("Mon..Fri 7..17/2".parse().unwrap()),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_query_string() {
+        let config = sample_config();
+        let query = config.to_query_string();
+        let decoded = SharedConfig::from_query_string(&query).unwrap();
+        assert_eq!(decoded, config);
+    }
+
+    #[test]
+    fn round_trips_without_a_schedule() {
+        let config = SharedConfig {
+            schedule: None,
+            ..sample_config()
+        };
+        let query = config.to_query_string();
+        let decoded = SharedConfig::from_query_string(&query).unwrap();
+        assert_eq!(decoded, config);
+    }
+
+    #[test]
+    fn missing_config_param_returns_none() {
+        assert!(SharedConfig::from_query_string("?other=1").is_none());
+    }
+
+    #[test]
+    fn garbage_config_param_returns_none() {
+        assert!(SharedConfig::from_query_string("?config=not-a-valid-config").is_none());
+    }
+}
@@ -1,10 +1,12 @@
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, TimeDelta, Utc};
+use chrono::{DateTime, NaiveDate, TimeDelta, Utc};
+use chrono_tz::Tz;
 // Loaded for LocalStorage methods.
 use gloo_storage::{errors::StorageError, Storage as AnyNameThatDoesNotConflictAAA};
 use protobuf::Message;
 
 use super::protos::storage::StorageMessage;
+use super::schedule::Schedule;
 
 const STORAGE_KEY: &str = "storage_message_proto";
 
@@ -65,6 +67,51 @@ impl Storage {
         self.save().context("Failed to save start interval")
     }
 
+    // Returns the user's chosen named timezone (e.g. "Asia/Tokyo"). None
+    // when nothing has been saved yet, or the saved name is no longer
+    // recognized.
+    pub fn get_timezone(&self) -> Option<Tz> {
+        let timezone_name = self.message.timezone_name.as_ref()?;
+        timezone_name.parse().ok()
+    }
+
+    pub fn set_timezone(&mut self, timezone: Tz) -> Result<()> {
+        self.message.timezone_name = Some(timezone.name().to_string());
+        self.save().context("Failed to save timezone")
+    }
+
+    // Returns the user's configured weekday/hour schedule, if any has been
+    // saved and it still parses.
+    pub fn get_schedule(&self) -> Option<Schedule> {
+        self.message.schedule.as_ref()?.parse().ok()
+    }
+
+    pub fn set_schedule(&mut self, schedule: Option<&Schedule>) -> Result<()> {
+        self.message.schedule = schedule.map(Schedule::to_string);
+        self.save().context("Failed to save schedule")
+    }
+
+    pub fn get_notifications_enabled(&self) -> bool {
+        self.message.notifications_enabled.unwrap_or(false)
+    }
+
+    pub fn set_notifications_enabled(&mut self, enabled: bool) -> Result<()> {
+        self.message.notifications_enabled = Some(enabled);
+        self.save().context("Failed to save notifications_enabled")
+    }
+
+    // Returns the date a start-time notification was last sent, if any has
+    // been recorded and it still parses.
+    pub fn get_last_notified_date(&self) -> Option<NaiveDate> {
+        let last_notified_date = self.message.last_notified_date.as_ref()?;
+        NaiveDate::parse_from_str(last_notified_date, "%Y-%m-%d").ok()
+    }
+
+    pub fn set_last_notified_date(&mut self, date: NaiveDate) -> Result<()> {
+        self.message.last_notified_date = Some(date.format("%Y-%m-%d").to_string());
+        self.save().context("Failed to save last notified date")
+    }
+
     fn save(&self) -> Result<()> {
         let message = self
             .message